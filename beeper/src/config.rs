@@ -0,0 +1,36 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// The hot-reloadable subset of `Args` - loaded from a TOML file and watched
+/// at runtime so these can change without restarting the daemon. Any field
+/// left unset here keeps whatever was previously in effect.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct Settings {
+    pub blink_interval: Option<u64>,
+    pub interval: Option<u64>,
+    pub max_age_days: Option<i64>,
+    pub exclude_archived: Option<bool>,
+    pub exclude_muted: Option<bool>,
+}
+
+impl Settings {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let settings: Settings = toml::from_str(&contents)?;
+        Ok(settings)
+    }
+
+    /// Layers `overlay`'s set fields over `self`, keeping `self`'s value for
+    /// anything `overlay` leaves unset
+    pub fn merged(&self, overlay: &Settings) -> Settings {
+        Settings {
+            blink_interval: overlay.blink_interval.or(self.blink_interval),
+            interval: overlay.interval.or(self.interval),
+            max_age_days: overlay.max_age_days.or(self.max_age_days),
+            exclude_archived: overlay.exclude_archived.or(self.exclude_archived),
+            exclude_muted: overlay.exclude_muted.or(self.exclude_muted),
+        }
+    }
+}
@@ -1,14 +1,32 @@
-use led_controller::LedController;
-use clap::Parser;
+mod config;
+
+use led_controller::{BleBackend, LedController, LedSet};
+use clap::{Parser, ValueEnum};
+use config::Settings;
+use notify::Watcher;
 use reqwest::Client;
 use serde::Deserialize;
-use std::time::Duration as StdDuration;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+use tokio::sync::{mpsc, watch};
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
 
+/// Cap on queued filesystem-watcher events awaiting a reload - extras are
+/// dropped rather than left to grow unbounded, since a full reload always
+/// picks up the latest file contents anyway
+const GLOBAL_BACKLOG: usize = 32;
+
+/// How long to wait after the last filesystem event before reparsing, so a
+/// flurry of editor writes collapses into a single reload
+const RELOAD_DEBOUNCE: StdDuration = StdDuration::from_millis(300);
+
 #[derive(Parser)]
 #[command(name = "beeper-led-blinker")]
 #[command(version = "0.1.0")]
@@ -18,18 +36,44 @@ struct Args {
     #[arg(long, env)]
     token: String,
 
-    /// LED device path
+    /// LED device path (used when --sink=sysfs)
     #[arg(long, default_value = "/sys/class/leds/input3::capslock/brightness")]
     led_path: String,
 
+    /// Where to flash unread notifications - a keyboard LED or a BLE smart bulb
+    #[arg(long, value_enum, default_value = "sysfs")]
+    sink: SinkKind,
+
+    /// BLE device id or advertised name to match (used when --sink=ble)
+    #[arg(long)]
+    ble_device: Option<String>,
+
+    /// BLE GATT service UUID the bulb advertises (used when --sink=ble)
+    #[arg(long)]
+    ble_service: Option<String>,
+
+    /// BLE GATT characteristic UUID to write on/off to (used when --sink=ble)
+    #[arg(long)]
+    ble_characteristic: Option<String>,
+
     /// API base URL
     #[arg(long, default_value = "http://localhost:23373")]
     api_url: String,
 
-    /// Check interval in seconds
+    /// Unread-count check interval in seconds
     #[arg(long, default_value = "5")]
     interval: u64,
 
+    /// API-health ping interval in seconds - kept independent of --interval
+    /// so a slow unread poll doesn't delay noticing the API went down
+    #[arg(long, default_value = "5")]
+    health_interval: u64,
+
+    /// LED re-sync interval in seconds - periodically re-applies the desired
+    /// LED state to recover from external writers clobbering the sysfs value
+    #[arg(long, default_value = "60")]
+    led_resync_interval: u64,
+
     /// Blink interval in milliseconds
     #[arg(long, default_value = "500")]
     blink_interval: u64,
@@ -45,6 +89,119 @@ struct Args {
     /// Filter out messages from muted chats
     #[arg(long, default_value = "true")]
     exclude_muted: bool,
+
+    /// Load blink_interval/interval/max_age_days/exclude_archived/exclude_muted
+    /// from a TOML file, taking precedence over the flags above. Watched at
+    /// runtime: edits are picked up and applied without restarting.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Route a messaging network's unread count to its own sysfs LED
+    /// instead of one shared LED, e.g. --led-map whatsapp=/sys/class/leds/a
+    /// --led-map signal=/sys/class/leds/b. Repeatable. Each mapped LED
+    /// blinks at a distinct speed so they can be told apart at a glance.
+    /// Networks are parsed from the prefix of a message's chat id
+    /// (Beeper chat ids are namespaced per bridge, e.g. "whatsapp:...").
+    #[arg(long = "led-map")]
+    led_map: Vec<String>,
+}
+
+/// Added between each --led-map entry's blink interval (sorted by network
+/// name) so networks are distinguishable by blink speed alone
+const LED_MAP_BLINK_STAGGER_MS: u64 = 150;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SinkKind {
+    Sysfs,
+    Ble,
+}
+
+async fn build_led_controller(args: &Args) -> Result<LedController> {
+    match args.sink {
+        SinkKind::Sysfs => LedController::new(args.led_path.clone(), args.blink_interval),
+        SinkKind::Ble => {
+            let device = args
+                .ble_device
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("--ble-device is required when --sink=ble"))?;
+            let service = args
+                .ble_service
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("--ble-service is required when --sink=ble"))?
+                .parse()?;
+            let characteristic = args
+                .ble_characteristic
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("--ble-characteristic is required when --sink=ble"))?
+                .parse()?;
+
+            let backend = BleBackend::discover(device, service, characteristic).await?;
+            LedController::with_backend(Box::new(backend), args.blink_interval)
+        }
+    }
+}
+
+/// Parses `--led-map` entries (`network=path`) into `(network, led_path)` pairs
+fn parse_led_map(entries: &[String]) -> Result<Vec<(String, String)>> {
+    entries
+        .iter()
+        .map(|entry| {
+            entry
+                .split_once('=')
+                .map(|(network, path)| (network.to_string(), path.to_string()))
+                .ok_or_else(|| anyhow::anyhow!("--led-map entry '{}' must be 'network=path'", entry))
+        })
+        .collect()
+}
+
+/// Builds the set of LEDs to drive: one sysfs LED per `--led-map` entry
+/// (keyed by network, staggered in blink speed so they're distinguishable),
+/// or a single LED from --sink/--led-path/--ble-* under the key "default"
+async fn build_led_set(args: &Args) -> Result<LedSet> {
+    let mut set = LedSet::new();
+
+    if args.led_map.is_empty() {
+        set.add("default".to_string(), build_led_controller(args).await?, 0);
+        return Ok(set);
+    }
+
+    let mut entries = parse_led_map(&args.led_map)?;
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (index, (network, led_path)) in entries.into_iter().enumerate() {
+        let offset = index as u64 * LED_MAP_BLINK_STAGGER_MS;
+        info!("[{}] LED: {} (blink interval {}ms)", network, led_path, args.blink_interval + offset);
+        let led = LedController::new(led_path, args.blink_interval + offset)?;
+        set.add(network, led, offset);
+    }
+
+    Ok(set)
+}
+
+/// Beeper chat ids are namespaced per messaging network/bridge, e.g.
+/// "whatsapp:1234@s.whatsapp.net" or "signal:abcd-1234" - the network is the
+/// prefix before the first `:`
+fn network_from_chat_id(chat_id: &str) -> String {
+    chat_id
+        .split_once(':')
+        .map(|(network, _)| network)
+        .unwrap_or("default")
+        .to_string()
+}
+
+/// Which named LEDs should be blinking for a given per-network unread count.
+/// Without `--led-map`, everything funnels into the single "default" LED.
+fn active_networks(args: &Args, counts: &HashMap<String, u32>) -> HashSet<String> {
+    if args.led_map.is_empty() {
+        let total: u32 = counts.values().sum();
+        if total > 0 {
+            std::iter::once("default".to_string()).collect()
+        } else {
+            HashSet::new()
+        }
+    } else {
+        counts.iter().filter(|&(_, &count)| count > 0).map(|(network, _)| network.clone()).collect()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,7 +214,6 @@ struct Message {
     #[allow(dead_code)]
     id: String,
     #[serde(rename = "chatID")]
-    #[allow(dead_code)]
     chat_id: String,
     #[allow(dead_code)]
     timestamp: DateTime<Utc>,
@@ -96,7 +252,14 @@ impl BeeperClient {
         }
     }
 
-    async fn get_recent_unread_count(&self, max_age_days: i64, exclude_archived: bool, exclude_muted: bool) -> Result<u32> {
+    /// Fetches unread messages and groups the count by messaging network
+    /// (parsed from each message's chat id prefix)
+    async fn get_recent_unread_counts_by_network(
+        &self,
+        max_age_days: i64,
+        exclude_archived: bool,
+        exclude_muted: bool,
+    ) -> Result<HashMap<String, u32>> {
         let url = format!("{}/v0/search-messages", self.api_url);
 
         let mut query_params = vec![
@@ -137,42 +300,141 @@ impl BeeperClient {
 
         let messages: SearchMessagesResponse = response.json().await?;
 
-        let total_unread = messages.items
-            .iter()
-            .filter(|msg| msg.is_unread)
-            .count() as u32;
+        let mut network_counts: HashMap<String, u32> = HashMap::new();
+        for msg in &messages.items {
+            if msg.is_unread {
+                *network_counts.entry(network_from_chat_id(&msg.chat_id)).or_insert(0) += 1;
+            }
+        }
 
+        let total_unread: u32 = network_counts.values().sum();
         if total_unread > 0 {
-            debug!("Found {} unread messages", total_unread);
-
-            // Group by chat for better logging
-            use std::collections::HashMap;
-            let mut chat_counts: HashMap<&str, u32> = HashMap::new();
-            for msg in &messages.items {
-                if msg.is_unread {
-                    *chat_counts.entry(&msg.chat_id).or_insert(0) += 1;
-                }
-            }
+            debug!("Found {} unread messages across {} network(s)", total_unread, network_counts.len());
+        }
 
-            debug!("  Unread messages across {} chats", chat_counts.len());
+        Ok(network_counts)
+    }
+}
+
+/// One of the independent periodic jobs the background scheduler drives
+#[derive(Debug, Clone, Copy)]
+enum Task {
+    UnreadCheck,
+    HealthPing,
+    LedResync,
+}
+
+/// A `Task` plus its own interval and the last time it ran - lets e.g. a 5s
+/// health ping and a 30s unread poll share one `tokio::select!` loop without
+/// coupling their cadences together. Modeled after the run-body pattern
+/// `lightning-background-processor` uses to multiplex several timers.
+struct ScheduledTask {
+    task: Task,
+    interval: StdDuration,
+    last_run: Instant,
+}
+
+impl ScheduledTask {
+    fn new(task: Task, interval: StdDuration) -> Self {
+        Self {
+            task,
+            interval,
+            last_run: Instant::now(),
         }
+    }
 
-        Ok(total_unread)
+    /// How long until this task is next due, saturating at zero if overdue
+    fn time_until_due(&self, now: Instant) -> StdDuration {
+        self.interval.saturating_sub(now.duration_since(self.last_run))
+    }
+
+    fn is_due(&self, now: Instant) -> bool {
+        now.duration_since(self.last_run) >= self.interval
     }
 }
 
-async fn wait_for_api(client: &BeeperClient) -> Result<()> {
+/// Watches `path` for changes and pushes successfully-reparsed `Settings`
+/// through the returned receiver. Filesystem events are debounced by
+/// `RELOAD_DEBOUNCE` using the same generation-counter pattern as
+/// dbus-monitor's throttle: each event bumps a counter, and a reload only
+/// fires if no newer event has landed by the time its timer expires.
+/// Invalid configs are logged and ignored, leaving the last good settings.
+fn spawn_config_watcher(path: PathBuf, initial: Settings) -> Result<watch::Receiver<Settings>> {
+    let (settings_tx, settings_rx) = watch::channel(initial);
+    let (event_tx, mut event_rx) = mpsc::channel::<()>(GLOBAL_BACKLOG);
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if res.is_ok() {
+            // Drop the event if the reload task is still catching up - a
+            // fresh reload always reads the file's latest contents anyway
+            let _ = event_tx.try_send(());
+        }
+    })?;
+    let watch_dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| std::path::Path::new("."));
+    watcher.watch(watch_dir, notify::RecursiveMode::NonRecursive)?;
+
+    tokio::spawn(async move {
+        let _watcher = watcher; // kept alive for as long as this task runs
+        let generation = Arc::new(AtomicU64::new(0));
+
+        while event_rx.recv().await.is_some() {
+            let expected_gen = generation.fetch_add(1, Ordering::SeqCst) + 1;
+            let generation = generation.clone();
+            let settings_tx = settings_tx.clone();
+            let path = path.clone();
+
+            tokio::spawn(async move {
+                sleep(RELOAD_DEBOUNCE).await;
+                if generation.load(Ordering::SeqCst) != expected_gen {
+                    return; // superseded by a newer event
+                }
+
+                match Settings::load(&path) {
+                    Ok(file_settings) => {
+                        let merged = settings_tx.borrow().merged(&file_settings);
+                        info!("Reloaded config from {}", path.display());
+                        let _ = settings_tx.send(merged);
+                    }
+                    Err(e) => {
+                        warn!("Invalid config at {} - keeping previous settings: {}", path.display(), e);
+                    }
+                }
+            });
+        }
+    });
+
+    Ok(settings_rx)
+}
+
+/// Outcome of [`wait_for_api`]: either the API came up, or a shutdown signal
+/// arrived while we were still waiting and the caller should bail out instead
+/// of looping forever.
+enum WaitOutcome {
+    Ready,
+    ShutdownRequested,
+}
+
+async fn wait_for_api(client: &BeeperClient, shutdown_rx: &mut watch::Receiver<bool>) -> Result<WaitOutcome> {
     info!("Waiting for Beeper Desktop API to be available...");
 
     loop {
         if client.is_api_available().await {
             info!("Beeper Desktop API is available at {}", client.api_url);
-            return Ok(());
+            return Ok(WaitOutcome::Ready);
         }
 
         info!("Beeper Desktop API not available - retrying in 10 seconds...");
         info!("Make sure Beeper Desktop is running and API is enabled in Settings > Developers");
-        sleep(StdDuration::from_secs(10)).await;
+
+        tokio::select! {
+            _ = sleep(StdDuration::from_secs(10)) => {}
+            _ = shutdown_rx.changed() => {
+                return Ok(WaitOutcome::ShutdownRequested);
+            }
+        }
     }
 }
 
@@ -181,10 +443,45 @@ async fn main() -> Result<()> {
     // Initialize tracing
     tracing_subscriber::fmt::init();
 
-    let args = Args::parse();
+    let mut args = Args::parse();
+
+    // Load the hot-reloadable settings (blink_interval, interval,
+    // max_age_days, exclude_archived/muted), optionally watching --config
+    // for edits; flags above act as the defaults a config file can override
+    let cli_settings = Settings {
+        blink_interval: Some(args.blink_interval),
+        interval: Some(args.interval),
+        max_age_days: Some(args.max_age_days),
+        exclude_archived: Some(args.exclude_archived),
+        exclude_muted: Some(args.exclude_muted),
+    };
+
+    let mut settings_rx = if let Some(config_path) = args.config.clone() {
+        let initial = match Settings::load(&config_path) {
+            Ok(file_settings) => cli_settings.merged(&file_settings),
+            Err(e) => {
+                warn!("Failed to load config {}: {} - using CLI defaults for now", config_path.display(), e);
+                cli_settings
+            }
+        };
+        info!("Watching {} for config changes", config_path.display());
+        spawn_config_watcher(config_path, initial)?
+    } else {
+        watch::channel(cli_settings).1
+    };
+
+    let settings = settings_rx.borrow_and_update().clone();
+    args.blink_interval = settings.blink_interval.unwrap_or(args.blink_interval);
+    args.interval = settings.interval.unwrap_or(args.interval);
+    args.max_age_days = settings.max_age_days.unwrap_or(args.max_age_days);
+    args.exclude_archived = settings.exclude_archived.unwrap_or(args.exclude_archived);
+    args.exclude_muted = settings.exclude_muted.unwrap_or(args.exclude_muted);
 
     info!("Starting Beeper LED blinker");
-    info!("LED path: {}", args.led_path);
+    info!("Sink: {:?}", args.sink);
+    if matches!(args.sink, SinkKind::Sysfs) {
+        info!("LED path: {}", args.led_path);
+    }
     info!("API URL: {}", args.api_url);
     info!("Check interval: {}s", args.interval);
     if args.max_age_days > 0 {
@@ -195,67 +492,195 @@ async fn main() -> Result<()> {
     info!("Exclude archived chats: {}", args.exclude_archived);
     info!("Exclude muted chats: {}", args.exclude_muted);
 
-    // Initialize LED controller
-    let mut led = LedController::new(args.led_path, args.blink_interval)?;
+    // Initialize the LED(s) - one shared LED, or one per --led-map network
+    let mut led_set = build_led_set(&args).await?;
 
     // Initialize Beeper client
     let beeper = BeeperClient::new(args.api_url, args.token);
 
+    // Ctrl-C triggers a graceful shutdown instead of an abrupt kill - wired up
+    // before wait_for_api so Ctrl-C is honored even if the API never comes up
+    let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            let _ = shutdown_tx.send(true);
+        }
+    });
+
     // Wait for API to be available
-    wait_for_api(&beeper).await?;
+    if matches!(wait_for_api(&beeper, &mut shutdown_rx).await?, WaitOutcome::ShutdownRequested) {
+        info!("Shutdown signal received while waiting for API, exiting");
+        return Ok(());
+    }
 
     // Check initial state
-    let initial_unread = beeper.get_recent_unread_count(args.max_age_days, args.exclude_archived, args.exclude_muted).await?;
-    let mut currently_blinking = false;
+    let initial_counts = beeper
+        .get_recent_unread_counts_by_network(args.max_age_days, args.exclude_archived, args.exclude_muted)
+        .await?;
+    let initial_total: u32 = initial_counts.values().sum();
 
-    if initial_unread > 0 {
-        info!("Starting with {} unread messages - enabling LED", initial_unread);
-        led.start_blinking().await?;
-        currently_blinking = true;
+    if initial_total > 0 {
+        info!("Starting with {} unread messages - enabling LED(s)", initial_total);
     } else {
-        info!("No recent unread messages - LED off");
-        led.set_led_state(false)?;
+        info!("No recent unread messages - LED(s) off");
     }
+    led_set.sync(&active_networks(&args, &initial_counts)).await?;
 
     info!("Monitoring Beeper Desktop API for unread messages...");
+    info!(
+        "Scheduler: unread every {}s, health ping every {}s, LED re-sync every {}s",
+        args.interval, args.health_interval, args.led_resync_interval
+    );
+
+    // Independent periodic jobs, each on its own cadence - the select loop
+    // below sleeps only until the soonest-due one, then runs every task
+    // whose deadline has passed
+    let mut tasks = vec![
+        ScheduledTask::new(Task::UnreadCheck, StdDuration::from_secs(args.interval)),
+        ScheduledTask::new(Task::HealthPing, StdDuration::from_secs(args.health_interval)),
+        ScheduledTask::new(Task::LedResync, StdDuration::from_secs(args.led_resync_interval)),
+    ];
+    let fastest_timer = tasks.iter().map(|t| t.interval).min().unwrap();
 
     // Main monitoring loop
-    loop {
-        sleep(StdDuration::from_secs(args.interval)).await;
-
-        // Check if API is still available
-        if !beeper.is_api_available().await {
-            warn!("Beeper Desktop API became unavailable");
-            if currently_blinking {
-                info!("Stopping LED blink due to API unavailability");
-                led.stop_blinking()?;
-                currently_blinking = false;
+    'main_loop: loop {
+        let now = Instant::now();
+        let next_due = tasks
+            .iter()
+            .map(|t| t.time_until_due(now))
+            .min()
+            .unwrap_or(fastest_timer);
+
+        tokio::select! {
+            _ = sleep(next_due) => {}
+            _ = shutdown_rx.changed() => {
+                info!("Shutdown signal received, stopping");
+                break;
             }
+            Ok(()) = settings_rx.changed() => {
+                let new_settings = settings_rx.borrow_and_update().clone();
 
-            warn!("Waiting for API to reconnect...");
-            wait_for_api(&beeper).await?;
-            continue;
-        }
+                if let Some(new_interval) = new_settings.blink_interval {
+                    info!("Config reload: blink interval changed to {}ms", new_interval);
+                    led_set.set_base_blink_interval(new_interval).await?;
+                }
 
-        // Get current unread count
-        match beeper.get_recent_unread_count(args.max_age_days, args.exclude_archived, args.exclude_muted).await {
-            Ok(unread_count) => {
-                if unread_count > 0 && !currently_blinking {
-                    info!("Found {} unread messages - starting LED blink", unread_count);
-                    led.start_blinking().await?;
-                    currently_blinking = true;
-                } else if unread_count == 0 && currently_blinking {
-                    info!("No unread messages - stopping LED blink");
-                    led.stop_blinking()?;
-                    currently_blinking = false;
-                } else if unread_count > 0 {
-                    debug!("Still have {} unread messages - LED continues blinking", unread_count);
+                if let Some(new_interval) = new_settings.interval {
+                    if let Some(task) = tasks.iter_mut().find(|t| matches!(t.task, Task::UnreadCheck)) {
+                        if task.interval != StdDuration::from_secs(new_interval) {
+                            info!("Config reload: unread-check interval changed to {}s", new_interval);
+                            task.interval = StdDuration::from_secs(new_interval);
+                        }
+                    }
                 }
+
+                args.max_age_days = new_settings.max_age_days.unwrap_or(args.max_age_days);
+                args.exclude_archived = new_settings.exclude_archived.unwrap_or(args.exclude_archived);
+                args.exclude_muted = new_settings.exclude_muted.unwrap_or(args.exclude_muted);
             }
-            Err(e) => {
-                error!("Failed to get unread count: {}", e);
-                // Don't change LED state on API errors
+        }
+
+        let now = Instant::now();
+        for scheduled in tasks.iter_mut() {
+            if !scheduled.is_due(now) {
+                continue;
             }
+            scheduled.last_run = now;
+
+            match scheduled.task {
+                Task::HealthPing => {
+                    if !beeper.is_api_available().await {
+                        warn!("Beeper Desktop API became unavailable");
+                        info!("Stopping LED blink due to API unavailability");
+                        led_set.sync(&HashSet::new()).await?;
+
+                        warn!("Waiting for API to reconnect...");
+                        if matches!(wait_for_api(&beeper, &mut shutdown_rx).await?, WaitOutcome::ShutdownRequested) {
+                            info!("Shutdown signal received while waiting for API to reconnect");
+                            break 'main_loop;
+                        }
+                    }
+                }
+
+                Task::UnreadCheck => {
+                    match beeper.get_recent_unread_counts_by_network(args.max_age_days, args.exclude_archived, args.exclude_muted).await {
+                        Ok(counts) => {
+                            if !args.led_map.is_empty() {
+                                for network in counts.keys() {
+                                    if counts[network] > 0 && !led_set.names().any(|n| n == network) {
+                                        warn!("Unread messages on network '{}' have no --led-map entry - no LED will blink for them", network);
+                                    }
+                                }
+                            }
+
+                            let total: u32 = counts.values().sum();
+                            if total > 0 {
+                                debug!("{} unread message(s) across {} network(s)", total, counts.len());
+                            }
+                            led_set.sync(&active_networks(&args, &counts)).await?;
+                        }
+                        Err(e) => {
+                            error!("Failed to get unread count: {}", e);
+                            // Don't change LED state on API errors
+                        }
+                    }
+                }
+
+                Task::LedResync => {
+                    debug!("Re-syncing LED state");
+                    if let Err(e) = led_set.resync().await {
+                        warn!("Failed to re-sync LED state: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_from_chat_id_splits_on_first_colon() {
+        assert_eq!(network_from_chat_id("whatsapp:1234@s.whatsapp.net"), "whatsapp");
+        assert_eq!(network_from_chat_id("signal:abcd-1234"), "signal");
+    }
+
+    #[test]
+    fn network_from_chat_id_falls_back_to_default_without_a_colon() {
+        assert_eq!(network_from_chat_id("no-prefix-here"), "default");
+    }
+
+    fn args_with_led_map(led_map: &[&str]) -> Args {
+        let mut argv = vec!["beeper-led-blinker".to_string(), "--token".to_string(), "t".to_string()];
+        for entry in led_map {
+            argv.push("--led-map".to_string());
+            argv.push(entry.to_string());
         }
+        Args::try_parse_from(argv).unwrap()
+    }
+
+    #[test]
+    fn active_networks_without_led_map_collapses_to_single_default_led() {
+        let args = args_with_led_map(&[]);
+        let counts = HashMap::from([("whatsapp".to_string(), 2), ("signal".to_string(), 0)]);
+        assert_eq!(active_networks(&args, &counts), HashSet::from(["default".to_string()]));
+    }
+
+    #[test]
+    fn active_networks_without_led_map_is_empty_when_nothing_unread() {
+        let args = args_with_led_map(&[]);
+        let counts = HashMap::from([("whatsapp".to_string(), 0)]);
+        assert_eq!(active_networks(&args, &counts), HashSet::new());
+    }
+
+    #[test]
+    fn active_networks_with_led_map_routes_per_network() {
+        let args = args_with_led_map(&["whatsapp=/sys/a", "signal=/sys/b"]);
+        let counts = HashMap::from([("whatsapp".to_string(), 2), ("signal".to_string(), 0)]);
+        assert_eq!(active_networks(&args, &counts), HashSet::from(["whatsapp".to_string()]));
     }
 }
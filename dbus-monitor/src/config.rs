@@ -0,0 +1,127 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Top-level `--config` file: one or more independent rules, each mapping a
+/// set of notification filters to its own LED. Mirrors the per-device /
+/// per-button module layout other dotfile daemons use, just scoped to
+/// notification rules instead of input devices.
+#[derive(Debug, Deserialize)]
+pub struct ConfigFile {
+    /// Defaults applied to any rule that doesn't override them
+    #[serde(default)]
+    pub global: GlobalOptions,
+
+    pub rules: Vec<RuleConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GlobalOptions {
+    pub case_insensitive: Option<bool>,
+    pub blink_interval: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RuleConfig {
+    /// Human-readable name used in logs, e.g. "urgent-capslock"
+    pub name: String,
+
+    /// Regex patterns - the rule matches if any pattern matches the
+    /// notification's app name, summary or body
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    /// System-sensor conditions (e.g. "battery<20", "cputemp>80"), sampled on
+    /// the periodic check alongside notification filters - the rule matches
+    /// if any pattern OR any sensor condition is true
+    #[serde(default)]
+    pub sensors: Vec<String>,
+
+    #[serde(default)]
+    pub case_insensitive: Option<bool>,
+
+    #[serde(default)]
+    pub blink_interval: Option<u64>,
+
+    /// Named pattern ("solid"/"slow"/"fast"/"double-pulse") or a custom
+    /// on/off millisecond sequence (e.g. an SOS pattern), starting with "on"
+    #[serde(default)]
+    pub pattern: Option<BlinkPatternConfig>,
+
+    /// When multiple rules match at once on the same LED, the
+    /// highest-priority one wins and the others are suppressed until it clears
+    #[serde(default)]
+    pub priority: i32,
+
+    pub led: LedConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum BlinkPatternConfig {
+    Named(String),
+    Custom(Vec<u64>),
+}
+
+impl BlinkPatternConfig {
+    pub fn resolve(&self) -> Result<led_controller::BlinkPattern> {
+        match self {
+            BlinkPatternConfig::Named(name) => led_controller::BlinkPattern::from_name(name)
+                .ok_or_else(|| anyhow::anyhow!("unknown blink pattern '{}'", name)),
+            BlinkPatternConfig::Custom(millis) => {
+                if millis.is_empty() {
+                    anyhow::bail!("custom blink pattern must have at least one step");
+                }
+                let sequence = millis
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ms)| (i % 2 == 0, std::time::Duration::from_millis(*ms)))
+                    .collect();
+                Ok(led_controller::BlinkPattern::Custom(sequence))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum LedConfig {
+    Sysfs {
+        led_path: String,
+    },
+    Ble {
+        device: String,
+        service: String,
+        characteristic: String,
+    },
+}
+
+impl LedConfig {
+    /// Identifies the physical LED this config targets, so rules aimed at
+    /// the same light can be arbitrated by priority.
+    pub fn key(&self) -> String {
+        match self {
+            LedConfig::Sysfs { led_path } => format!("sysfs:{}", led_path),
+            LedConfig::Ble { device, .. } => format!("ble:{}", device),
+        }
+    }
+}
+
+impl ConfigFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let config: ConfigFile = serde_json::from_str(&contents)?;
+        Ok(config)
+    }
+
+    /// Resolves a rule's effective `case_insensitive` / `blink_interval`,
+    /// falling back to the `global` section.
+    pub fn effective_case_insensitive(&self, rule: &RuleConfig) -> bool {
+        rule.case_insensitive.or(self.global.case_insensitive).unwrap_or(false)
+    }
+
+    pub fn effective_blink_interval(&self, rule: &RuleConfig) -> u64 {
+        rule.blink_interval.or(self.global.blink_interval).unwrap_or(500)
+    }
+}
@@ -1,14 +1,24 @@
+mod config;
+mod notify;
+mod sensors;
+
 use anyhow::Result;
-use clap::Parser;
-use led_controller::LedController;
+use clap::{Parser, ValueEnum};
+use config::{ConfigFile, LedConfig};
+use led_controller::{BleBackend, LedController};
+use notify::Notify;
 use regex::Regex;
+use sensors::SensorRule;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::process::Stdio;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration as StdDuration;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
@@ -19,13 +29,36 @@ use tracing::{debug, info, warn};
 struct Args {
     /// Text filter patterns (regex) - LED blinks when any notification matches
     /// Can be specified multiple times: --filter "urgent" --filter "error"
-    #[arg(long, required = true)]
+    /// Ignored (and not required) when --config is given.
+    #[arg(long)]
     filter: Vec<String>,
 
-    /// LED device path
+    /// System-sensor condition (e.g. "battery<20", "cputemp>80") - LED also
+    /// blinks whenever this is true. Sampled on the periodic check.
+    /// Can be specified multiple times. Ignored when --config is given.
+    #[arg(long)]
+    sensor: Vec<String>,
+
+    /// LED device path (used when --led-backend=sysfs)
     #[arg(long, default_value = "/sys/class/leds/input3::capslock/brightness")]
     led_path: String,
 
+    /// Which LED backend to drive
+    #[arg(long, value_enum, default_value = "sysfs")]
+    led_backend: LedBackendKind,
+
+    /// BLE device id or advertised name to match (used when --led-backend=ble)
+    #[arg(long)]
+    ble_device: Option<String>,
+
+    /// BLE GATT service UUID the bulb advertises (used when --led-backend=ble)
+    #[arg(long)]
+    ble_service: Option<String>,
+
+    /// BLE GATT characteristic UUID to write on/off to (used when --led-backend=ble)
+    #[arg(long)]
+    ble_characteristic: Option<String>,
+
     /// Blink interval in milliseconds
     #[arg(long, default_value = "500")]
     blink_interval: u64,
@@ -34,9 +67,74 @@ struct Args {
     #[arg(long, default_value = "false")]
     case_insensitive: bool,
 
-    /// Check interval in seconds (for periodic sync)
-    #[arg(long, default_value = "3")]
+    /// Fallback periodic check in seconds - re-samples sensors and re-syncs
+    /// LED state as a safety net, and checks SwayNC is still available.
+    /// Notification-driven state changes no longer wait on this; they wake
+    /// the LED task immediately instead.
+    #[arg(long, default_value = "30")]
     interval: u64,
+
+    /// Load rules from a JSON config file instead of --filter/--led-path.
+    /// Each rule gets its own filters and its own LED; see `config.rs`.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Debounce LED state changes by this many milliseconds: a desired-state
+    /// change is only applied if it's still the desired state once the timer
+    /// fires, coalescing rapid notification bursts into a single transition
+    #[arg(long)]
+    throttle: Option<u64>,
+
+    /// Skip spawning dbus-monitor entirely and rely solely on the periodic
+    /// check (at --interval). No notifications are seen in this mode, so
+    /// --filter patterns never match; only --sensor conditions drive the LED
+    #[arg(long, default_value = "false")]
+    poll_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LedBackendKind {
+    Sysfs,
+    Ble,
+}
+
+async fn build_led_from_config(led: &LedConfig, blink_interval: u64) -> Result<LedController> {
+    match led {
+        LedConfig::Sysfs { led_path } => LedController::new(led_path.clone(), blink_interval),
+        LedConfig::Ble {
+            device,
+            service,
+            characteristic,
+        } => {
+            let backend = BleBackend::discover(device.clone(), service.parse()?, characteristic.parse()?).await?;
+            LedController::with_backend(Box::new(backend), blink_interval)
+        }
+    }
+}
+
+async fn build_led_controller(args: &Args) -> Result<LedController> {
+    match args.led_backend {
+        LedBackendKind::Sysfs => LedController::new(args.led_path.clone(), args.blink_interval),
+        LedBackendKind::Ble => {
+            let device = args
+                .ble_device
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("--ble-device is required when --led-backend=ble"))?;
+            let service = args
+                .ble_service
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("--ble-service is required when --led-backend=ble"))?
+                .parse()?;
+            let characteristic = args
+                .ble_characteristic
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("--ble-characteristic is required when --led-backend=ble"))?
+                .parse()?;
+
+            let backend = BleBackend::discover(device, service, characteristic).await?;
+            LedController::with_backend(Box::new(backend), args.blink_interval)
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -50,10 +148,13 @@ struct Notification {
 struct NotificationTracker {
     notifications: Arc<RwLock<HashMap<u32, Notification>>>,
     filters: Arc<Vec<Regex>>,
+    /// Pinged after every add/remove so the LED task wakes immediately
+    /// instead of waiting for the next periodic check
+    notify: Arc<Notify>,
 }
 
 impl NotificationTracker {
-    fn new(filter_patterns: Vec<String>, case_insensitive: bool) -> Result<Self> {
+    fn new(filter_patterns: Vec<String>, case_insensitive: bool, notify: Arc<Notify>) -> Result<Self> {
         let mut filters = Vec::new();
         for pattern in &filter_patterns {
             let regex = if case_insensitive {
@@ -68,6 +169,7 @@ impl NotificationTracker {
         Ok(Self {
             notifications: Arc::new(RwLock::new(HashMap::new())),
             filters: Arc::new(filters),
+            notify,
         })
     }
 
@@ -94,24 +196,41 @@ impl NotificationTracker {
 
         let mut notifications = self.notifications.write().await;
         notifications.insert(id, notification);
+        drop(notifications);
+
+        self.notify.notify_one();
 
         matches
     }
 
     async fn remove_notification(&self, id: u32) -> bool {
         let mut notifications = self.notifications.write().await;
-        if let Some(notification) = notifications.remove(&id) {
+        let removed = notifications.remove(&id);
+        drop(notifications);
+
+        if let Some(notification) = removed {
             let was_matching = self.matches_filter(&notification);
             if was_matching {
                 info!("Removed matching notification {}", id);
             } else {
                 debug!("Removed non-matching notification {}", id);
             }
+            self.notify.notify_one();
             return was_matching;
         }
         false
     }
 
+    /// Drops every tracked notification, e.g. after a `NotificationClosed`
+    /// signal whose id we can't reliably correlate
+    async fn clear(&self) {
+        let mut notifications = self.notifications.write().await;
+        notifications.clear();
+        drop(notifications);
+
+        self.notify.notify_one();
+    }
+
     async fn has_matching_notifications(&self) -> bool {
         let notifications = self.notifications.read().await;
         notifications.values().any(|n| self.matches_filter(n))
@@ -135,6 +254,241 @@ impl NotificationTracker {
     }
 }
 
+/// One independent filter -> LED binding: its own tracker, its own LED, its
+/// own blink pattern and priority. A notification is routed through every
+/// `RuleRunner`, and `reconcile` arbitrates rules that target the same
+/// physical LED (`led_key`) by priority.
+struct RuleRunner {
+    name: String,
+    tracker: NotificationTracker,
+    sensors: Vec<SensorRule>,
+    led: LedController,
+    led_key: String,
+    priority: i32,
+    currently_blinking: bool,
+    /// Last sensor reading, refreshed only by `sample_sensors` on the
+    /// periodic fallback tick - `has_matching` reads this instead of
+    /// re-sampling so a burst of notifications doesn't also burst blocking
+    /// battery/thermal-zone reads
+    sensor_triggered: bool,
+    /// Last desired state computed by `reconcile` - may be ahead of
+    /// `currently_blinking` while a throttle timer is pending
+    desired: bool,
+    /// Bumped every time `desired` changes, so a pending throttle timer can
+    /// tell whether it's still current when it fires
+    generation: Arc<AtomicU64>,
+}
+
+impl RuleRunner {
+    /// True if any notification filter matches, or the last sampled sensor
+    /// condition (e.g. "battery<20") was true. Cheap - does no sensor I/O -
+    /// so it's safe to call on every notify wakeup; see `sample_sensors` for
+    /// where the sensor reading itself gets refreshed
+    async fn has_matching(&self) -> bool {
+        self.tracker.has_matching_notifications().await || self.sensor_triggered
+    }
+
+    /// Re-samples every `--sensor` condition and caches whether any of them
+    /// is currently true. Only called from the periodic fallback tick -
+    /// each sample does blocking sync I/O (battery manager, sysfs thermal
+    /// reads), so it must not run on every notification add/remove/clear
+    async fn sample_sensors(&mut self) {
+        let mut triggered = false;
+        for sensor in &self.sensors {
+            match sensor.is_triggered().await {
+                Ok(true) => triggered = true,
+                Ok(false) => {}
+                Err(e) => warn!("[{}] Failed to sample sensor: {}", self.name, e),
+            }
+        }
+        self.sensor_triggered = triggered;
+    }
+
+    /// Applies `desired` to the LED immediately (bypassing any throttle)
+    async fn apply(&mut self) {
+        if self.desired {
+            self.activate().await;
+        } else {
+            self.deactivate().await;
+        }
+    }
+
+    async fn activate(&mut self) {
+        if !self.currently_blinking {
+            info!("[{}] Starting LED blink", self.name);
+            if let Err(e) = self.led.start_blinking().await {
+                warn!("[{}] Failed to start LED: {}", self.name, e);
+            } else {
+                self.currently_blinking = true;
+            }
+        }
+    }
+
+    async fn deactivate(&mut self) {
+        if self.currently_blinking {
+            info!("[{}] Stopping LED blink", self.name);
+            if let Err(e) = self.led.stop_blinking().await {
+                warn!("[{}] Failed to stop LED: {}", self.name, e);
+            } else {
+                self.currently_blinking = false;
+            }
+        }
+    }
+}
+
+/// Coalesces rapid LED state changes: schedules the actual transition after
+/// `delay`, and only applies it if `desired` hasn't changed again by then
+/// (tracked via each rule's `generation` counter). Mirrors `action_throttle`
+/// debouncing in file-watcher tools.
+#[derive(Clone)]
+struct Throttle {
+    tx: mpsc::Sender<usize>,
+    delay: StdDuration,
+}
+
+impl Throttle {
+    fn schedule(&self, index: usize, generation: Arc<AtomicU64>, expected_gen: u64) {
+        let tx = self.tx.clone();
+        let delay = self.delay;
+        tokio::spawn(async move {
+            sleep(delay).await;
+            if generation.load(Ordering::SeqCst) == expected_gen {
+                let _ = tx.send(index).await;
+            }
+        });
+    }
+}
+
+/// Re-checks every rule's matching state and arbitrates rules that target
+/// the same `led_key`: the highest-priority rule that currently matches wins
+/// and drives the LED; the rest are suppressed (stopped) until it clears.
+/// If `throttle` is set, state changes are debounced instead of applied
+/// immediately; call `apply_throttled` when its timer fires.
+async fn reconcile(rules: &mut [RuleRunner], throttle: Option<&Throttle>) {
+    let mut matching = Vec::with_capacity(rules.len());
+    for rule in rules.iter() {
+        let has_matching = rule.has_matching().await;
+        if has_matching {
+            let count = rule.tracker.count_matching_notifications().await;
+            debug!("[{}] {} matching notifications (priority={})", rule.name, count, rule.priority);
+        }
+        matching.push(has_matching);
+    }
+
+    let mut winners: HashMap<String, usize> = HashMap::new();
+    for (i, rule) in rules.iter().enumerate() {
+        if !matching[i] {
+            continue;
+        }
+        match winners.get(&rule.led_key) {
+            Some(&current) if rules[current].priority >= rule.priority => {}
+            _ => {
+                winners.insert(rule.led_key.clone(), i);
+            }
+        }
+    }
+
+    let winning_indices: std::collections::HashSet<usize> = winners.values().copied().collect();
+
+    for (i, rule) in rules.iter_mut().enumerate() {
+        let new_desired = winning_indices.contains(&i);
+        if new_desired == rule.desired {
+            continue;
+        }
+        rule.desired = new_desired;
+
+        match throttle {
+            Some(throttle) => {
+                let gen = rule.generation.fetch_add(1, Ordering::SeqCst) + 1;
+                throttle.schedule(i, rule.generation.clone(), gen);
+            }
+            None => rule.apply().await,
+        }
+    }
+}
+
+/// Applies a rule's (possibly now-stale-looking, but actually still current)
+/// desired state once its throttle timer has fired
+async fn apply_throttled(rules: &mut [RuleRunner], index: usize) {
+    if let Some(rule) = rules.get_mut(index) {
+        rule.apply().await;
+    }
+}
+
+/// Builds the set of rules to run: one per entry in `--config` if given,
+/// otherwise a single rule from the legacy `--filter`/`--led-path` flags.
+async fn build_rules(args: &Args, notify: Arc<Notify>) -> Result<Vec<RuleRunner>> {
+    if let Some(config_path) = &args.config {
+        let config = ConfigFile::load(config_path)?;
+        let mut rules = Vec::with_capacity(config.rules.len());
+
+        for rule in &config.rules {
+            let case_insensitive = config.effective_case_insensitive(rule);
+            let blink_interval = config.effective_blink_interval(rule);
+
+            info!(
+                "[{}] Filters: {:?}, sensors: {:?} (case_insensitive={}, priority={})",
+                rule.name, rule.patterns, rule.sensors, case_insensitive, rule.priority
+            );
+
+            let tracker = NotificationTracker::new(rule.patterns.clone(), case_insensitive, notify.clone())?;
+            let sensors = rule
+                .sensors
+                .iter()
+                .map(|s| SensorRule::from_str(s))
+                .collect::<Result<Vec<_>>>()?;
+            let mut led = build_led_from_config(&rule.led, blink_interval).await?;
+            if let Some(pattern) = &rule.pattern {
+                led.set_pattern(pattern.resolve()?);
+            }
+
+            rules.push(RuleRunner {
+                name: rule.name.clone(),
+                tracker,
+                sensors,
+                led,
+                led_key: rule.led.key(),
+                priority: rule.priority,
+                currently_blinking: false,
+                sensor_triggered: false,
+                desired: false,
+                generation: Arc::new(AtomicU64::new(0)),
+            });
+        }
+
+        Ok(rules)
+    } else {
+        if args.filter.is_empty() && args.sensor.is_empty() {
+            anyhow::bail!("--filter or --sensor is required unless --config is given");
+        }
+
+        let tracker = NotificationTracker::new(args.filter.clone(), args.case_insensitive, notify)?;
+        let sensors = args
+            .sensor
+            .iter()
+            .map(|s| SensorRule::from_str(s))
+            .collect::<Result<Vec<_>>>()?;
+        let led = build_led_controller(args).await?;
+        let led_key = match args.led_backend {
+            LedBackendKind::Sysfs => format!("sysfs:{}", args.led_path),
+            LedBackendKind::Ble => format!("ble:{}", args.ble_device.clone().unwrap_or_default()),
+        };
+
+        Ok(vec![RuleRunner {
+            name: "default".to_string(),
+            tracker,
+            sensors,
+            led,
+            led_key,
+            priority: 0,
+            currently_blinking: false,
+            sensor_triggered: false,
+            desired: false,
+            generation: Arc::new(AtomicU64::new(0)),
+        }])
+    }
+}
+
 async fn is_swaync_running() -> bool {
     let result = Command::new("swaync-client")
         .arg("--count")
@@ -190,39 +544,87 @@ async fn main() -> Result<()> {
     let args = Args::parse();
 
     info!("Starting DBus Notification LED blinker");
-    info!("LED path: {}", args.led_path);
-    info!("Filters: {:?}", args.filter);
-    info!("Case insensitive: {}", args.case_insensitive);
+    if args.config.is_none() {
+        info!("LED backend: {:?}", args.led_backend);
+        if matches!(args.led_backend, LedBackendKind::Sysfs) {
+            info!("LED path: {}", args.led_path);
+        }
+        info!("Filters: {:?}", args.filter);
+        info!("Sensors: {:?}", args.sensor);
+        info!("Case insensitive: {}", args.case_insensitive);
+    }
 
-    // Initialize LED controller
-    let mut led = LedController::new(args.led_path.clone(), args.blink_interval)?;
+    // Shared wakeup queue: the dbus parser pings this after every tracked
+    // notification add/remove so the LED task below reacts immediately
+    // instead of waiting for the next periodic check
+    let notify = Arc::new(Notify::new());
+
+    // Build the rules to run - one per `--config` entry, or a single rule
+    // from the legacy --filter/--led-path flags
+    let mut rules = build_rules(&args, notify.clone()).await?;
+    info!("Running {} rule(s)", rules.len());
+
+    // --poll-only never starts dbus-monitor, so NotificationTracker is never
+    // fed an add_notification() - a rule with notification filters and no
+    // --sensor condition can therefore never match. Bail instead of leaving
+    // the LED silently dead.
+    if args.poll_only {
+        let dead: Vec<&str> = rules
+            .iter()
+            .filter(|r| !r.tracker.filters.is_empty() && r.sensors.is_empty())
+            .map(|r| r.name.as_str())
+            .collect();
+        if !dead.is_empty() {
+            anyhow::bail!(
+                "--poll-only relies solely on the periodic sync and never sees dbus notifications, \
+                 but rule(s) {:?} only have --filter patterns and no --sensor condition - they can \
+                 never match. Add a --sensor rule to those, or drop --poll-only.",
+                dead
+            );
+        }
+    }
 
-    // Initialize notification tracker
-    let tracker = NotificationTracker::new(args.filter.clone(), args.case_insensitive)?;
+    // Set up the throttle channel (only consumed if --throttle is set)
+    let (throttle_tx, mut throttle_rx) = mpsc::channel::<usize>(32);
+    let throttle = args.throttle.map(|ms| Throttle {
+        tx: throttle_tx.clone(),
+        delay: StdDuration::from_millis(ms),
+    });
+    if let Some(ms) = args.throttle {
+        info!("Throttling LED state changes by {}ms", ms);
+    }
+    drop(throttle_tx);
 
     // Wait for SwayNC to be available
     wait_for_swaync().await?;
 
-    info!("Starting dbus-monitor to track notifications...");
-
-    // Start dbus-monitor as a subprocess
-    let mut child = Command::new("dbus-monitor")
-        .arg("--session")
-        .arg("interface='org.freedesktop.Notifications'")
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .spawn()?;
-
-    let stdout = child.stdout.take().ok_or_else(|| {
-        anyhow::anyhow!("Failed to capture stdout from dbus-monitor")
-    })?;
-
-    let mut reader = BufReader::new(stdout).lines();
-
-    info!("Monitoring notifications via dbus-monitor...");
-    info!("Waiting for notifications that match filters...");
+    let (mut child, mut reader) = if args.poll_only {
+        info!("--poll-only: skipping dbus-monitor, relying solely on periodic sync");
+        (None, None)
+    } else {
+        info!("Starting dbus-monitor to track notifications...");
+        let mut child = Command::new("dbus-monitor")
+            .arg("--session")
+            .arg("interface='org.freedesktop.Notifications'")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let stdout = child.stdout.take().ok_or_else(|| {
+            anyhow::anyhow!("Failed to capture stdout from dbus-monitor")
+        })?;
+
+        info!("Monitoring notifications via dbus-monitor...");
+        info!("Waiting for notifications that match filters...");
+
+        (Some(child), Some(BufReader::new(stdout).lines()))
+    };
+
+    // Kept across loop iterations so it stays registered while the dbus
+    // branch's handler runs - a fresh listen() per iteration would already be
+    // dropped by then and miss the trackers' notify_one() calls
+    let mut listener = Box::pin(notify.listen());
 
-    let mut currently_blinking = false;
     let mut pending_notification: Option<(Option<String>, Option<String>, Option<String>)> = None;
     let mut in_notify_call = false;
     let mut in_notify_return = false;
@@ -231,8 +633,8 @@ async fn main() -> Result<()> {
     // Main loop
     loop {
         tokio::select! {
-            // Read lines from dbus-monitor
-            line = reader.next_line() => {
+            // Read lines from dbus-monitor (disabled entirely in --poll-only mode)
+            line = reader.as_mut().unwrap().next_line(), if reader.is_some() => {
                 match line {
                     Ok(Some(line)) => {
                         debug!("DBus: {}", line);
@@ -294,17 +696,15 @@ async fn main() -> Result<()> {
                                         info!("New notification #{}: app='{}', summary='{}', body='{}'",
                                             id, app_name, summary_text, body_text);
 
-                                        let matched = tracker.add_notification(id, app_name, summary_text, body_text).await;
-
-                                        if matched && !currently_blinking {
-                                            info!("Starting LED blink - matching notification detected");
-                                            if let Err(e) = led.start_blinking().await {
-                                                warn!("Failed to start LED blinking: {}", e);
-                                            } else {
-                                                currently_blinking = true;
-                                            }
+                                        // Route the notification through every rule's tracker;
+                                        // each one pings `notify` so the LED task below wakes
+                                        // immediately and arbitrates rules sharing an LED
+                                        for rule in rules.iter_mut() {
+                                            rule.tracker
+                                                .add_notification(id, app_name.clone(), summary_text.clone(), body_text.clone())
+                                                .await;
                                         }
-                                        
+
                                         // Reset state
                                         in_notify_call = false;
                                         pending_notification = None;
@@ -316,20 +716,10 @@ async fn main() -> Result<()> {
                         else if in_close_signal {
                             if let Some(_id) = parse_dbus_uint32(&line, "uint32 ") {
                                 info!("Notification closed - clearing all tracked notifications");
-                                // Since we can't reliably track notification IDs, clear everything
-                                // The periodic check will re-sync if there are still matching notifications
-                                {
-                                    let mut notifications = tracker.notifications.write().await;
-                                    notifications.clear();
-                                }
-                                
-                                if currently_blinking {
-                                    info!("Stopping LED blink - will re-check in periodic sync");
-                                    if let Err(e) = led.stop_blinking() {
-                                        warn!("Failed to stop LED blinking: {}", e);
-                                    } else {
-                                        currently_blinking = false;
-                                    }
+                                // Since we can't reliably track notification IDs, clear everything.
+                                // Each tracker pings `notify`, waking the LED task below to re-sync.
+                                for rule in rules.iter_mut() {
+                                    rule.tracker.clear().await;
                                 }
 
                                 in_close_signal = false;
@@ -340,17 +730,18 @@ async fn main() -> Result<()> {
                         warn!("dbus-monitor ended, restarting...");
                         sleep(StdDuration::from_secs(5)).await;
 
-                        child = Command::new("dbus-monitor")
+                        let mut new_child = Command::new("dbus-monitor")
                             .arg("--session")
                             .arg("interface='org.freedesktop.Notifications'")
                             .stdout(Stdio::piped())
                             .stderr(Stdio::null())
                             .spawn()?;
 
-                        let stdout = child.stdout.take().ok_or_else(|| {
+                        let stdout = new_child.stdout.take().ok_or_else(|| {
                             anyhow::anyhow!("Failed to capture stdout from dbus-monitor")
                         })?;
-                        reader = BufReader::new(stdout).lines();
+                        child = Some(new_child);
+                        reader = Some(BufReader::new(stdout).lines());
                     }
                     Err(e) => {
                         warn!("Error reading from dbus-monitor: {}", e);
@@ -359,44 +750,43 @@ async fn main() -> Result<()> {
                 }
             }
 
-            // Periodic check
+            // Woken immediately by a tracker's add/remove/clear - recompute
+            // matching state and arbitrate, same as the periodic fallback below
+            _ = &mut listener => {
+                listener.set(notify.listen());
+                debug!("Woken by notification state change");
+                reconcile(&mut rules, throttle.as_ref()).await;
+            }
+
+            // Fallback periodic check: re-samples sensors, re-syncs state as a
+            // safety net, and checks SwayNC is still available
             _ = sleep(StdDuration::from_secs(args.interval)) => {
-                debug!("Performing periodic check");
+                debug!("Performing periodic fallback check");
 
                 if !is_swaync_running().await {
                     warn!("SwayNC became unavailable");
-                    if currently_blinking {
-                        info!("Stopping LED due to SwayNC unavailability");
-                        if let Err(e) = led.stop_blinking() {
-                            warn!("Failed to stop LED: {}", e);
-                        } else {
-                            currently_blinking = false;
-                        }
+                    for rule in rules.iter_mut() {
+                        rule.deactivate().await;
+                        rule.desired = false;
                     }
                     wait_for_swaync().await?;
                 }
 
-                // Check if LED state matches notification state
-                let has_matching = tracker.has_matching_notifications().await;
-                let count = tracker.count_matching_notifications().await;
-
-                if has_matching && !currently_blinking {
-                    info!("Sync: Starting LED blink ({} matching notifications)", count);
-                    if let Err(e) = led.start_blinking().await {
-                        warn!("Failed to start LED: {}", e);
-                    } else {
-                        currently_blinking = true;
-                    }
-                } else if !has_matching && currently_blinking {
-                    info!("Sync: Stopping LED blink (no matching notifications)");
-                    if let Err(e) = led.stop_blinking() {
-                        warn!("Failed to stop LED: {}", e);
-                    } else {
-                        currently_blinking = false;
-                    }
-                } else if has_matching {
-                    debug!("Sync: {} matching notifications, LED blinking", count);
+                // Refresh the cached sensor reading for each rule - this is
+                // the only place sensors get sampled, so a burst of
+                // unrelated notifications never triggers blocking sensor I/O
+                for rule in rules.iter_mut() {
+                    rule.sample_sensors().await;
                 }
+
+                // Re-sync every rule's LED against its own matching state,
+                // arbitrating rules that share an LED by priority
+                reconcile(&mut rules, throttle.as_ref()).await;
+            }
+
+            // A throttled state change's debounce timer fired
+            Some(index) = throttle_rx.recv(), if throttle.is_some() => {
+                apply_throttled(&mut rules, index).await;
             }
         }
     }
@@ -0,0 +1,248 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Registered, not yet woken
+const UNNOTIFIED: u8 = 0;
+/// Woken by `notify_one()`, not yet observed by a `poll()`
+const NOTIFIED_ONE: u8 = 1;
+/// Observed - the `Listener` future has resolved
+const RESOLVED: u8 = 2;
+
+struct Entry {
+    id: u64,
+    flag: Arc<AtomicU8>,
+    waker: Waker,
+}
+
+struct NotifyState {
+    listeners: VecDeque<Entry>,
+    next_id: u64,
+}
+
+/// A minimal single-producer wakeup queue: `notify_one()` wakes the oldest
+/// pending `listen()`er immediately, rather than making it wait out a fixed
+/// polling interval. Lets the LED task react to changes the dbus parser makes
+/// to the shared `Arc<RwLock<HashMap>>` without waiting on a periodic sleep.
+pub struct Notify {
+    state: Mutex<NotifyState>,
+}
+
+impl Notify {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(NotifyState {
+                listeners: VecDeque::new(),
+                next_id: 0,
+            }),
+        }
+    }
+
+    /// Returns a future that resolves the next time `notify_one()` is called
+    /// after it has first been polled; calls made before that first poll are
+    /// not seen by it
+    pub fn listen(&self) -> Listener<'_> {
+        Listener {
+            notify: self,
+            id: None,
+            flag: None,
+        }
+    }
+
+    /// Wakes the oldest still-pending listener, if any. A no-op if nobody
+    /// is currently listening - callers recompute full state on wakeup, so a
+    /// notification with no listener registered is safe to drop.
+    pub fn notify_one(&self) {
+        let mut state = self.state.lock().unwrap();
+        while let Some(entry) = state.listeners.pop_front() {
+            if entry
+                .flag
+                .compare_exchange(UNNOTIFIED, NOTIFIED_ONE, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                entry.waker.wake();
+                return;
+            }
+            // Entry was already resolved/dropped between being queued and
+            // popped here - move on to the next one instead of losing the wakeup.
+        }
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A pending `Notify::listen()` call. Polling registers (or re-registers)
+/// this listener's waker; dropping it before it resolves removes its entry
+/// and, if it had already been notified, forwards the wakeup to the next
+/// waiter so it is never silently lost.
+pub struct Listener<'a> {
+    notify: &'a Notify,
+    id: Option<u64>,
+    flag: Option<Arc<AtomicU8>>,
+}
+
+impl Future for Listener<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if let Some(flag) = self.flag.clone() {
+            match flag.load(Ordering::SeqCst) {
+                NOTIFIED_ONE => {
+                    flag.store(RESOLVED, Ordering::SeqCst);
+                    Poll::Ready(())
+                }
+                RESOLVED => Poll::Ready(()),
+                _ => {
+                    // Still waiting - keep the registered waker current.
+                    let mut state = self.notify.state.lock().unwrap();
+                    let id = self.id.unwrap();
+                    if let Some(entry) = state.listeners.iter_mut().find(|e| e.id == id) {
+                        entry.waker = cx.waker().clone();
+                    }
+                    Poll::Pending
+                }
+            }
+        } else {
+            let mut state = self.notify.state.lock().unwrap();
+            let id = state.next_id;
+            state.next_id += 1;
+            let flag = Arc::new(AtomicU8::new(UNNOTIFIED));
+            state.listeners.push_back(Entry {
+                id,
+                flag: flag.clone(),
+                waker: cx.waker().clone(),
+            });
+            drop(state);
+
+            self.id = Some(id);
+            self.flag = Some(flag);
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for Listener<'_> {
+    fn drop(&mut self) {
+        let (Some(id), Some(flag)) = (self.id, &self.flag) else {
+            return;
+        };
+
+        let mut state = self.notify.state.lock().unwrap();
+        if let Some(pos) = state.listeners.iter().position(|e| e.id == id) {
+            // Never notified - just deregister to avoid leaking a stale waker.
+            state.listeners.remove(pos);
+            return;
+        }
+        drop(state);
+
+        // Already popped by notify_one(), but dropped before this listener
+        // observed it via poll() - pass the wakeup on so it isn't lost.
+        if flag.swap(RESOLVED, Ordering::SeqCst) == NOTIFIED_ONE {
+            self.notify.notify_one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::task::{RawWaker, RawWakerVTable};
+
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw()
+        }
+        fn noop(_: *const ()) {}
+        fn raw() -> RawWaker {
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        unsafe { Waker::from_raw(raw()) }
+    }
+
+    #[test]
+    fn notify_before_poll_is_dropped_not_stored() {
+        // notify_one() with nobody registered yet must not panic and must
+        // not leave behind state that wrongly resolves the next listener.
+        let notify = Notify::new();
+        notify.notify_one();
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut listener = Box::pin(notify.listen());
+        assert_eq!(listener.as_mut().poll(&mut cx), Poll::Pending);
+    }
+
+    #[test]
+    fn notify_one_wakes_the_oldest_pending_listener() {
+        let notify = Notify::new();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut a = Box::pin(notify.listen());
+        let mut b = Box::pin(notify.listen());
+        assert_eq!(a.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(b.as_mut().poll(&mut cx), Poll::Pending);
+
+        notify.notify_one();
+        assert_eq!(a.as_mut().poll(&mut cx), Poll::Ready(()));
+        assert_eq!(b.as_mut().poll(&mut cx), Poll::Pending);
+    }
+
+    #[test]
+    fn dropping_a_notified_but_unresolved_listener_forwards_to_the_next_waiter() {
+        let notify = Notify::new();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut a = Box::pin(notify.listen());
+        let mut b = Box::pin(notify.listen());
+        assert_eq!(a.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(b.as_mut().poll(&mut cx), Poll::Pending);
+
+        // Wakes `a`, but `a` is dropped before it gets a chance to observe
+        // the wakeup via poll() - the wakeup must not be lost.
+        notify.notify_one();
+        drop(a);
+
+        assert_eq!(b.as_mut().poll(&mut cx), Poll::Ready(()));
+    }
+
+    #[test]
+    fn notify_one_with_no_listeners_is_a_noop() {
+        let notify = Notify::new();
+        // Must not panic, and must not leave a phantom wakeup for whoever
+        // registers next.
+        notify.notify_one();
+        notify.notify_one();
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut listener = Box::pin(notify.listen());
+        assert_eq!(listener.as_mut().poll(&mut cx), Poll::Pending);
+    }
+
+    #[tokio::test]
+    async fn listen_resolves_once_notified_one_is_called_from_another_task() {
+        let notify = Arc::new(Notify::new());
+
+        let waiter = notify.clone();
+        let handle = tokio::spawn(async move { waiter.listen().await });
+
+        // Give the spawned task a chance to register before we notify it.
+        tokio::task::yield_now().await;
+        notify.notify_one();
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), handle)
+            .await
+            .expect("listen() should resolve promptly after notify_one()")
+            .unwrap();
+    }
+}
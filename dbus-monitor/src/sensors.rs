@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Result};
+use futures_lite::StreamExt;
+use std::str::FromStr;
+use tracing::debug;
+
+/// A hardware condition sampled on the periodic tick and folded into the
+/// same has-matching/LED logic as notification filters, e.g. `battery<20`
+/// or `cputemp>80`.
+#[derive(Debug, Clone, Copy)]
+pub struct SensorRule {
+    metric: SensorMetric,
+    comparison: Comparison,
+    threshold: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SensorMetric {
+    /// Battery charge, percent (0-100)
+    Battery,
+    /// Hottest thermal zone, degrees Celsius
+    CpuTemp,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    LessThan,
+    GreaterThan,
+}
+
+impl FromStr for SensorRule {
+    type Err = anyhow::Error;
+
+    fn from_str(expr: &str) -> Result<Self> {
+        let (metric_str, comparison, threshold_str) = if let Some((m, t)) = expr.split_once('<') {
+            (m, Comparison::LessThan, t)
+        } else if let Some((m, t)) = expr.split_once('>') {
+            (m, Comparison::GreaterThan, t)
+        } else {
+            return Err(anyhow!("sensor rule '{}' must contain '<' or '>', e.g. 'battery<20'", expr));
+        };
+
+        let metric = match metric_str.trim() {
+            "battery" => SensorMetric::Battery,
+            "cputemp" => SensorMetric::CpuTemp,
+            other => return Err(anyhow!("unknown sensor metric '{}' (expected 'battery' or 'cputemp')", other)),
+        };
+
+        let threshold: f64 = threshold_str.trim().parse()?;
+
+        Ok(SensorRule {
+            metric,
+            comparison,
+            threshold,
+        })
+    }
+}
+
+impl SensorRule {
+    /// Samples the underlying sensor and checks it against the threshold
+    pub async fn is_triggered(&self) -> Result<bool> {
+        let value = match self.metric {
+            SensorMetric::Battery => sample_battery_percent().await?,
+            SensorMetric::CpuTemp => sample_cpu_temp_celsius().await?,
+        };
+
+        let triggered = match self.comparison {
+            Comparison::LessThan => value < self.threshold,
+            Comparison::GreaterThan => value > self.threshold,
+        };
+
+        debug!("Sensor {:?} = {:.1} (threshold {:?} {}) -> {}", self.metric, value, self.comparison, self.threshold, triggered);
+
+        Ok(triggered)
+    }
+}
+
+/// Reads the primary battery's state of charge as a percentage (0-100)
+async fn sample_battery_percent() -> Result<f64> {
+    let manager = battery::Manager::new()?;
+    let battery = manager
+        .batteries()?
+        .next()
+        .ok_or_else(|| anyhow!("no battery found"))??;
+
+    Ok(battery.state_of_charge().value as f64 * 100.0)
+}
+
+/// Reads the hottest thermal zone's temperature in Celsius
+async fn sample_cpu_temp_celsius() -> Result<f64> {
+    let mut sensors = heim::sensors::temperatures();
+    let mut hottest: Option<f64> = None;
+
+    while let Some(sensor) = sensors.next().await {
+        let celsius = sensor?.current().celsius();
+        hottest = Some(hottest.map_or(celsius, |h: f64| h.max(celsius)));
+    }
+
+    hottest.ok_or_else(|| anyhow!("no thermal sensors found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_battery_less_than() {
+        let rule = SensorRule::from_str("battery<20").unwrap();
+        assert_eq!(rule.metric, SensorMetric::Battery);
+        assert!(matches!(rule.comparison, Comparison::LessThan));
+        assert_eq!(rule.threshold, 20.0);
+    }
+
+    #[test]
+    fn parses_cputemp_greater_than() {
+        let rule = SensorRule::from_str("cputemp>80").unwrap();
+        assert_eq!(rule.metric, SensorMetric::CpuTemp);
+        assert!(matches!(rule.comparison, Comparison::GreaterThan));
+        assert_eq!(rule.threshold, 80.0);
+    }
+
+    #[test]
+    fn trims_whitespace_around_metric_and_threshold() {
+        let rule = SensorRule::from_str(" battery < 20 ").unwrap();
+        assert_eq!(rule.metric, SensorMetric::Battery);
+        assert_eq!(rule.threshold, 20.0);
+    }
+
+    #[test]
+    fn rejects_missing_comparison_operator() {
+        assert!(SensorRule::from_str("battery20").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_metric() {
+        assert!(SensorRule::from_str("humidity<20").is_err());
+    }
+
+    #[test]
+    fn rejects_non_numeric_threshold() {
+        assert!(SensorRule::from_str("battery<low").is_err());
+    }
+}
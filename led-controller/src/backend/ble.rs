@@ -0,0 +1,142 @@
+use super::LedBackend;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use bluest::{Adapter, Characteristic, Device, DeviceId, Uuid};
+use futures_lite::StreamExt;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+/// Drives a BLE smart bulb by writing to a GATT characteristic.
+///
+/// The bulb is found once at startup by scanning for `service` and matching
+/// `filter` against the advertised name (or device id, if `filter` parses as
+/// one). After that, only the `DeviceId` is kept: if the connection drops,
+/// a background task re-opens the adapter and reconnects by id so a blink
+/// request never hard-fails just because the bulb went to sleep.
+pub struct BleBackend {
+    adapter: Adapter,
+    device_id: DeviceId,
+    service: Uuid,
+    characteristic: Uuid,
+    device: Arc<Mutex<Option<Device>>>,
+    /// Resolved once and reused by `set_state` instead of re-discovering the
+    /// service/characteristic on every toggle - cleared by the reconnect
+    /// task whenever `device` is replaced, since a handle resolved against
+    /// the old connection is no longer valid.
+    resolved_characteristic: Arc<Mutex<Option<Characteristic>>>,
+}
+
+impl BleBackend {
+    /// Discovers a BLE device advertising `service` whose name or id matches
+    /// `filter`, and spawns a background task that keeps it connected.
+    pub async fn discover(filter: String, service: Uuid, characteristic: Uuid) -> Result<Self> {
+        let adapter = Adapter::default()
+            .await
+            .ok_or_else(|| anyhow!("no Bluetooth adapter found"))?;
+        adapter.wait_available().await?;
+
+        info!("Scanning for BLE device matching '{}' (service {})", filter, service);
+        let mut scan = adapter.discover_devices(&[service]).await?;
+        let device = loop {
+            let found = scan
+                .next()
+                .await
+                .ok_or_else(|| anyhow!("BLE scan ended without finding a matching device"))??;
+
+            let name = found.name().unwrap_or_default();
+            if name == filter || found.id().to_string() == filter {
+                break found;
+            }
+        };
+
+        let device_id = device.id();
+        adapter.connect_device(&device).await?;
+        info!("Connected to BLE device '{}' ({})", device.name().unwrap_or_default(), device_id);
+
+        let backend = Self {
+            adapter: adapter.clone(),
+            device_id: device_id.clone(),
+            service,
+            characteristic,
+            device: Arc::new(Mutex::new(Some(device))),
+            resolved_characteristic: Arc::new(Mutex::new(None)),
+        };
+
+        backend.spawn_reconnect_task();
+
+        Ok(backend)
+    }
+
+    /// Watches the connection and reconnects by `device_id` whenever it drops.
+    fn spawn_reconnect_task(&self) {
+        let adapter = self.adapter.clone();
+        let device_id = self.device_id.clone();
+        let device_slot = self.device.clone();
+        let characteristic_slot = self.resolved_characteristic.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let is_connected = {
+                    let guard = device_slot.lock().await;
+                    match guard.as_ref() {
+                        Some(device) => adapter.is_connected(device).await,
+                        None => false,
+                    }
+                };
+
+                if !is_connected {
+                    warn!("BLE bulb disconnected - reconnecting by id {}", device_id);
+                    match adapter.open_device(&device_id).await {
+                        Ok(device) => match adapter.connect_device(&device).await {
+                            Ok(()) => {
+                                info!("Reconnected to BLE bulb {}", device_id);
+                                *device_slot.lock().await = Some(device);
+                                // The cached characteristic was resolved against
+                                // the now-stale connection - re-resolve lazily.
+                                *characteristic_slot.lock().await = None;
+                            }
+                            Err(e) => error!("Failed to reconnect to BLE bulb: {}", e),
+                        },
+                        Err(e) => error!("Failed to re-open BLE bulb by id: {}", e),
+                    }
+                }
+
+                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl LedBackend for BleBackend {
+    async fn set_state(&mut self, on: bool) -> Result<()> {
+        let guard = self.device.lock().await;
+        let device = guard
+            .as_ref()
+            .ok_or_else(|| anyhow!("BLE bulb not currently connected - will retry once reconnected"))?;
+
+        let mut characteristic_guard = self.resolved_characteristic.lock().await;
+        if characteristic_guard.is_none() {
+            let service = device
+                .discover_services_with_uuid(self.service)
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow!("BLE bulb does not advertise service {}", self.service))?;
+
+            let characteristic = service
+                .discover_characteristics_with_uuid(self.characteristic)
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow!("BLE bulb does not expose characteristic {}", self.characteristic))?;
+
+            *characteristic_guard = Some(characteristic);
+        }
+
+        let value = if on { [0x01] } else { [0x00] };
+        characteristic_guard.as_ref().unwrap().write(&value).await?;
+        Ok(())
+    }
+}
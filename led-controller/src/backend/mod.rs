@@ -0,0 +1,32 @@
+mod sysfs;
+mod ble;
+
+pub use sysfs::SysfsBackend;
+pub use ble::BleBackend;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// An output that can be turned on/off and blinked.
+///
+/// Implementations own whatever resource actually represents the light
+/// (a sysfs file, a BLE characteristic, ...). `LedController` owns the
+/// blink timer and toggles the backend via `set_state`; `start_blinking`/
+/// `stop_blinking` are hooks a backend can use for setup/teardown around a
+/// blink session (e.g. making sure a BLE connection is up) and default to
+/// a no-op / turning the light off.
+#[async_trait]
+pub trait LedBackend: Send {
+    /// Sets the LED to on or off.
+    async fn set_state(&mut self, on: bool) -> Result<()>;
+
+    /// Called once when a blink session starts, before the first `set_state`.
+    async fn start_blinking(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called once when a blink session ends. The default turns the LED off.
+    async fn stop_blinking(&mut self) -> Result<()> {
+        self.set_state(false).await
+    }
+}
@@ -0,0 +1,80 @@
+use super::LedBackend;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Seek, SeekFrom, Write};
+use std::process::Command;
+use tracing::warn;
+
+/// Drives an LED by writing `0`/`1` to a sysfs brightness file.
+///
+/// Prefers a single write handle opened once at startup - each toggle is
+/// then one `write`, not a `fork`+`exec`+`wait` of `sudo tee`. Falls back to
+/// the subprocess path only if the direct open is denied permission.
+pub struct SysfsBackend {
+    led_path: String,
+    handle: Option<File>,
+}
+
+impl SysfsBackend {
+    /// Creates a new sysfs-backed LED, verifying it can be written to.
+    pub fn new(led_path: String) -> Result<Self> {
+        let handle = match OpenOptions::new().write(true).open(&led_path) {
+            Ok(file) => Some(file),
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+                warn!(
+                    "No direct write access to {} ({}) - falling back to `sudo tee` on every toggle, \
+                     which is far slower and jittier for blinking. For precise toggling without a process spawn per blink, \
+                     grant access instead: add a udev rule (e.g. `SUBSYSTEM==\"leds\", RUN+=\"/bin/chmod \
+                     666 /sys/class/leds/%k/brightness\"`) or run with CAP_DAC_OVERRIDE.",
+                    led_path, e
+                );
+                None
+            }
+            Err(e) => return Err(e).context(format!("Failed to open LED device {}", led_path)),
+        };
+
+        let mut backend = Self { led_path, handle };
+        backend.write_state(false)?;
+        Ok(backend)
+    }
+
+    fn write_state(&mut self, on: bool) -> Result<()> {
+        let state: &[u8] = if on { b"1" } else { b"0" };
+
+        match &mut self.handle {
+            Some(handle) => {
+                handle.seek(SeekFrom::Start(0))?;
+                handle.write_all(state)?;
+                handle.flush()?;
+                Ok(())
+            }
+            None => self.write_state_via_subprocess(state),
+        }
+    }
+
+    fn write_state_via_subprocess(&self, state: &[u8]) -> Result<()> {
+        let mut child = Command::new("sudo")
+            .arg("tee")
+            .arg(&self.led_path)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()?;
+
+        if let Some(stdin) = child.stdin.take() {
+            let mut stdin = stdin;
+            stdin.write_all(state)?;
+        }
+
+        child.wait()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LedBackend for SysfsBackend {
+    async fn set_state(&mut self, on: bool) -> Result<()> {
+        self.write_state(on)
+    }
+}
@@ -1,20 +1,34 @@
+mod backend;
+mod pattern;
+mod set;
+
+pub use backend::{BleBackend, LedBackend, SysfsBackend};
+pub use pattern::BlinkPattern;
+pub use set::LedSet;
+
 use anyhow::Result;
-use std::process::Command;
+use std::sync::Arc;
 use std::time::Duration as StdDuration;
-use tokio::sync::watch;
+use tokio::sync::{watch, Mutex};
 use tokio::time::sleep;
 use tracing::{debug, error, info};
 
-/// Controls an LED by writing to a sysfs brightness file
+/// Controls an LED through a pluggable `LedBackend`
 pub struct LedController {
-    led_path: String,
+    backend: Arc<Mutex<Box<dyn LedBackend>>>,
     blink_interval: u64,
+    /// The on/off cycle `start_blinking` repeats. Defaults to a symmetric
+    /// cycle derived from `blink_interval`; override with `set_pattern`.
+    pattern: Vec<(bool, StdDuration)>,
     is_blinking: bool,
     stop_tx: Option<watch::Sender<bool>>,
+    /// The on/off value the blink task most recently wrote, kept so
+    /// `reapply` can force-rewrite it without duplicating the blink task
+    current_state: Arc<Mutex<bool>>,
 }
 
 impl LedController {
-    /// Creates a new LED controller for the given LED device path
+    /// Creates a new LED controller backed by a sysfs brightness file
     ///
     /// # Arguments
     /// * `led_path` - Path to the LED brightness file (e.g., "/sys/class/leds/input3::capslock/brightness")
@@ -26,18 +40,26 @@ impl LedController {
     /// # Errors
     /// Returns an error if the LED device cannot be accessed or written to
     pub fn new(led_path: String, blink_interval: u64) -> Result<Self> {
-        let controller = Self {
-            led_path,
+        Self::with_backend(Box::new(SysfsBackend::new(led_path)?), blink_interval)
+    }
+
+    /// Creates a new LED controller using a custom `LedBackend` (e.g. a BLE bulb)
+    pub fn with_backend(backend: Box<dyn LedBackend>, blink_interval: u64) -> Result<Self> {
+        let interval = StdDuration::from_millis(blink_interval);
+        Ok(Self {
+            backend: Arc::new(Mutex::new(backend)),
             blink_interval,
+            pattern: vec![(true, interval), (false, interval)],
             is_blinking: false,
             stop_tx: None,
-        };
-
-        // Test LED access
-        controller.set_led_state(false)?;
-        info!("LED control permissions verified");
+            current_state: Arc::new(Mutex::new(false)),
+        })
+    }
 
-        Ok(controller)
+    /// Overrides the on/off cycle used by `start_blinking`. Has no effect on
+    /// a blink session already in progress; stop and restart to apply it.
+    pub fn set_pattern(&mut self, pattern: BlinkPattern) {
+        self.pattern = pattern.sequence();
     }
 
     /// Sets the LED to on or off
@@ -47,8 +69,8 @@ impl LedController {
     ///
     /// # Errors
     /// Returns an error if the LED state cannot be set
-    pub fn set_led_state(&self, on: bool) -> Result<()> {
-        Self::set_led_state_static(&self.led_path, on)
+    pub async fn set_led_state(&self, on: bool) -> Result<()> {
+        self.backend.lock().await.set_state(on).await
     }
 
     /// Starts blinking the LED using the configured blink interval
@@ -66,18 +88,21 @@ impl LedController {
             return Ok(());
         }
 
+        self.backend.lock().await.start_blinking().await?;
+
         self.is_blinking = true;
         info!("Starting LED blinking");
 
-        let led_path = self.led_path.clone();
-        let interval = StdDuration::from_millis(self.blink_interval);
+        let backend = self.backend.clone();
+        let pattern = self.pattern.clone();
+        let current_state = self.current_state.clone();
 
         // Create a channel to signal the task to stop
         let (stop_tx, mut stop_rx) = watch::channel(false);
         self.stop_tx = Some(stop_tx);
 
         tokio::spawn(async move {
-            let mut state = true;
+            let mut step = 0usize;
             loop {
                 // Check if we should stop
                 if *stop_rx.borrow() {
@@ -85,14 +110,17 @@ impl LedController {
                     break;
                 }
 
-                if let Err(e) = Self::set_led_state_static(&led_path, state) {
+                let (state, duration) = pattern[step % pattern.len()];
+                step = step.wrapping_add(1);
+
+                if let Err(e) = backend.lock().await.set_state(state).await {
                     error!("Failed to set LED state: {}", e);
                 }
-                state = !state;
+                *current_state.lock().await = state;
 
-                // Use tokio::select to wait for either the interval or stop signal
+                // Use tokio::select to wait for either this step's duration or the stop signal
                 tokio::select! {
-                    _ = sleep(interval) => {},
+                    _ = sleep(duration) => {},
                     _ = stop_rx.changed() => {
                         if *stop_rx.borrow() {
                             debug!("Blink task stopping");
@@ -103,7 +131,7 @@ impl LedController {
             }
 
             // Turn off LED when stopping
-            let _ = Self::set_led_state_static(&led_path, false);
+            let _ = backend.lock().await.set_state(false).await;
         });
 
         Ok(())
@@ -118,7 +146,7 @@ impl LedController {
     /// - If not currently blinking, this is a no-op
     /// - Signals the background blinking task to stop
     /// - Ensures LED is turned off
-    pub fn stop_blinking(&mut self) -> Result<()> {
+    pub async fn stop_blinking(&mut self) -> Result<()> {
         if !self.is_blinking {
             return Ok(());
         }
@@ -131,7 +159,22 @@ impl LedController {
             let _ = stop_tx.send(true);
         }
 
-        self.set_led_state(false)
+        self.backend.lock().await.stop_blinking().await
+    }
+
+    /// Force-rewrites the backend to the LED's current state, e.g. to
+    /// recover from an external writer clobbering the sysfs value.
+    ///
+    /// Unlike `start_blinking`, this never no-ops on `is_blinking` - while
+    /// blinking it re-sends whatever on/off value the blink task last wrote;
+    /// otherwise it re-sends off.
+    pub async fn reapply(&self) -> Result<()> {
+        let state = if self.is_blinking {
+            *self.current_state.lock().await
+        } else {
+            false
+        };
+        self.backend.lock().await.set_state(state).await
     }
 
     /// Returns whether the LED is currently blinking
@@ -144,7 +187,8 @@ impl LedController {
         self.blink_interval
     }
 
-    /// Sets a new blink interval in milliseconds
+    /// Sets a new symmetric blink interval in milliseconds, replacing any
+    /// pattern set via `set_pattern`
     ///
     /// # Arguments
     /// * `interval` - New blink interval in milliseconds
@@ -153,27 +197,7 @@ impl LedController {
     /// - If the LED is currently blinking, you need to stop and restart it for the new interval to take effect
     pub fn set_blink_interval(&mut self, interval: u64) {
         self.blink_interval = interval;
-    }
-
-    /// Internal helper to set LED state using sudo tee
-    fn set_led_state_static(led_path: &str, on: bool) -> Result<()> {
-        let state = if on { "1" } else { "0" };
-
-        let mut child = Command::new("sudo")
-            .arg("tee")
-            .arg(led_path)
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .spawn()?;
-
-        if let Some(stdin) = child.stdin.take() {
-            use std::io::Write;
-            let mut stdin = stdin;
-            stdin.write_all(state.as_bytes())?;
-        }
-
-        child.wait()?;
-        Ok(())
+        let duration = StdDuration::from_millis(interval);
+        self.pattern = vec![(true, duration), (false, duration)];
     }
 }
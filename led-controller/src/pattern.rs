@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+/// A named or custom blink pattern, expanded into an on/off cycle that
+/// `LedController` iterates over while blinking.
+#[derive(Debug, Clone)]
+pub enum BlinkPattern {
+    /// LED stays on, no toggling
+    Solid,
+    /// Long on/off halves
+    Slow,
+    /// Short on/off halves
+    Fast,
+    /// Two short flashes followed by a gap
+    DoublePulse,
+    /// Explicit on/off duration sequence, e.g. an SOS-style pattern
+    Custom(Vec<(bool, Duration)>),
+}
+
+impl BlinkPattern {
+    /// Expands this pattern into the `(state, duration)` cycle the blink
+    /// task repeats for as long as blinking is active.
+    pub fn sequence(&self) -> Vec<(bool, Duration)> {
+        match self {
+            BlinkPattern::Solid => vec![(true, Duration::from_secs(60 * 60))],
+            BlinkPattern::Slow => vec![(true, Duration::from_millis(800)), (false, Duration::from_millis(800))],
+            BlinkPattern::Fast => vec![(true, Duration::from_millis(120)), (false, Duration::from_millis(120))],
+            BlinkPattern::DoublePulse => vec![
+                (true, Duration::from_millis(100)),
+                (false, Duration::from_millis(100)),
+                (true, Duration::from_millis(100)),
+                (false, Duration::from_millis(600)),
+            ],
+            BlinkPattern::Custom(sequence) => sequence.clone(),
+        }
+    }
+
+    /// Parses a named pattern (`solid`, `slow`, `fast`, `double-pulse`). Use
+    /// `BlinkPattern::Custom` directly for an explicit on/off sequence.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "solid" => Some(BlinkPattern::Solid),
+            "slow" => Some(BlinkPattern::Slow),
+            "fast" => Some(BlinkPattern::Fast),
+            "double-pulse" => Some(BlinkPattern::DoublePulse),
+            _ => None,
+        }
+    }
+}
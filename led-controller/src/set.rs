@@ -0,0 +1,97 @@
+use crate::LedController;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+struct NamedLed {
+    led: LedController,
+    currently_blinking: bool,
+    /// Added to the set's base blink interval for this LED, so e.g. each
+    /// messaging network's LED blinks at a visibly different speed
+    blink_interval_offset: u64,
+}
+
+/// Manages a set of independently-blinking named LEDs - e.g. one per
+/// messaging network - each with its own backend and blink interval.
+/// `sync` diffs a set of "should be blinking" names against what's
+/// currently blinking and starts/stops only the LEDs that changed.
+pub struct LedSet {
+    leds: HashMap<String, NamedLed>,
+}
+
+impl LedSet {
+    pub fn new() -> Self {
+        Self { leds: HashMap::new() }
+    }
+
+    /// Adds a named LED to the set. `blink_interval_offset` is added to any
+    /// base interval passed to `set_base_blink_interval`, so this LED can be
+    /// told apart from others by blink speed. Not currently blinking until
+    /// `sync` includes its name in the active set.
+    pub fn add(&mut self, name: String, led: LedController, blink_interval_offset: u64) {
+        self.leds.insert(
+            name,
+            NamedLed {
+                led,
+                currently_blinking: false,
+                blink_interval_offset,
+            },
+        );
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.leds.keys().map(String::as_str)
+    }
+
+    /// Starts blinking every named LED in `active` that isn't already
+    /// blinking, and stops every one not in `active` that is
+    pub async fn sync(&mut self, active: &HashSet<String>) -> Result<()> {
+        for (name, named) in self.leds.iter_mut() {
+            let should_blink = active.contains(name);
+            if should_blink && !named.currently_blinking {
+                named.led.start_blinking().await?;
+                named.currently_blinking = true;
+            } else if !should_blink && named.currently_blinking {
+                named.led.stop_blinking().await?;
+                named.currently_blinking = false;
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-applies each LED's current state, e.g. to recover from an
+    /// external writer clobbering the sysfs value. Uses `reapply` rather
+    /// than `start_blinking`, which no-ops when the LED is already blinking,
+    /// and rather than `set_led_state`, which would write a fixed value
+    /// instead of the state the blink task last wrote.
+    pub async fn resync(&mut self) -> Result<()> {
+        for named in self.leds.values_mut() {
+            named.led.reapply().await?;
+        }
+        Ok(())
+    }
+
+    /// Rebases every LED's blink interval to `base + blink_interval_offset`,
+    /// restarting the blink task of any LED that's currently blinking so the
+    /// new interval takes effect immediately
+    pub async fn set_base_blink_interval(&mut self, base: u64) -> Result<()> {
+        for named in self.leds.values_mut() {
+            let interval = base + named.blink_interval_offset;
+            if named.led.blink_interval() == interval {
+                continue;
+            }
+
+            named.led.set_blink_interval(interval);
+            if named.currently_blinking {
+                named.led.stop_blinking().await?;
+                named.led.start_blinking().await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for LedSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}